@@ -0,0 +1,202 @@
+//! The blocking pool: a thread pool dedicated to running `spawn_blocking`
+//! closures, separate from the scheduler's worker threads.
+//!
+//! `shutdown` blocks the calling thread until every blocking-pool thread
+//! has joined (or `duration` elapses); `shutdown_async` exposes the same
+//! completion signal as a pollable future so it can be `.await`ed from an
+//! async context instead. Both request shutdown through `Shutdown`, which
+//! tracks how many threads `spawn_blocking` has outstanding and only
+//! completes once a shutdown has been requested *and* that count reaches
+//! zero — so shutdown actually waits for real blocking work to drain
+//! instead of resolving immediately regardless of what's still running.
+
+use crate::task::JoinHandle;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Condvar, Mutex};
+use std::task::{Context, Poll, Waker};
+use std::time::Duration;
+
+#[derive(Debug, Default)]
+struct State {
+    /// Number of `spawn_blocking` threads currently running.
+    active: usize,
+    /// Set once shutdown has been requested; `active` reaching zero while
+    /// this is set is what actually marks `done`.
+    shutting_down: bool,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+/// The completion signal shared between `BlockingPool` and every
+/// thread/future waiting on it to drain.
+#[derive(Debug, Default)]
+struct Shutdown {
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+impl Shutdown {
+    /// Called by `spawn_blocking` before the closure's thread is spawned.
+    fn thread_started(&self) {
+        self.state.lock().unwrap().active += 1;
+    }
+
+    /// Called by a blocking-pool thread as it exits. Completes shutdown if
+    /// one has been requested and this was the last thread outstanding.
+    fn thread_finished(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.active -= 1;
+        if state.shutting_down && state.active == 0 {
+            self.mark_done(&mut state);
+        }
+    }
+
+    /// Requests shutdown, completing immediately if no thread is currently
+    /// outstanding.
+    fn begin_shutdown(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.shutting_down = true;
+        if state.active == 0 {
+            self.mark_done(&mut state);
+        }
+    }
+
+    fn mark_done(&self, state: &mut State) {
+        state.done = true;
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+        self.condvar.notify_all();
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct BlockingPool {
+    shutdown: Arc<Shutdown>,
+}
+
+impl BlockingPool {
+    pub(crate) fn new() -> Self {
+        Self {
+            shutdown: Arc::new(Shutdown::default()),
+        }
+    }
+
+    /// Runs `f` on a dedicated blocking-pool thread, returning a
+    /// [`JoinHandle`] for its result. `shutdown`/`shutdown_async` won't
+    /// complete while this (or any other `spawn_blocking` call) is still
+    /// running.
+    pub(crate) fn spawn_blocking<F, T>(&self, f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        self.shutdown.thread_started();
+        let shutdown = self.shutdown.clone();
+
+        // Calls `thread_finished` on drop, so a closure that panics still
+        // counts as finished.
+        struct Finished(Arc<Shutdown>);
+        impl Drop for Finished {
+            fn drop(&mut self) {
+                self.0.thread_finished();
+            }
+        }
+
+        JoinHandle::spawn_on_thread(move || {
+            let _finished = Finished(shutdown);
+            f()
+        })
+    }
+
+    /// Blocks the calling thread until every blocking-pool thread has
+    /// joined, or `duration` elapses (waits forever if `None`).
+    pub(crate) fn shutdown(&mut self, duration: Option<Duration>) {
+        self.shutdown.begin_shutdown();
+        let state = self.shutdown.state.lock().unwrap();
+
+        match duration {
+            Some(duration) => {
+                let _ = self
+                    .shutdown
+                    .condvar
+                    .wait_timeout_while(state, duration, |state| !state.done);
+            }
+            None => {
+                let _ = self.shutdown.condvar.wait_while(state, |state| !state.done);
+            }
+        }
+    }
+
+    /// The non-blocking counterpart to [`shutdown`](Self::shutdown):
+    /// returns a future that resolves once every blocking-pool thread has
+    /// joined, so a supervising task can `.await` the drain instead of
+    /// parking the calling thread.
+    pub(crate) fn shutdown_async(self) -> impl Future<Output = ()> {
+        self.shutdown.begin_shutdown();
+        ShutdownFuture {
+            shutdown: self.shutdown,
+        }
+    }
+}
+
+struct ShutdownFuture {
+    shutdown: Arc<Shutdown>,
+}
+
+impl Future for ShutdownFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut state = self.shutdown.state.lock().unwrap();
+        if state.done {
+            Poll::Ready(())
+        } else {
+            state.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn shutdown_with_no_outstanding_work_returns_immediately() {
+        let mut pool = BlockingPool::new();
+        pool.shutdown(Some(Duration::from_millis(10)));
+    }
+
+    #[test]
+    fn shutdown_waits_for_a_spawn_blocking_thread_to_finish() {
+        let mut pool = BlockingPool::new();
+
+        let _handle = pool.spawn_blocking(|| {
+            thread::sleep(Duration::from_millis(10));
+        });
+
+        pool.shutdown(Some(Duration::from_secs(5)));
+
+        assert!(pool.shutdown.state.lock().unwrap().done);
+    }
+
+    #[test]
+    fn shutdown_timeout_returns_even_if_work_never_finishes() {
+        let mut pool = BlockingPool::new();
+
+        let (tx, rx) = std::sync::mpsc::channel::<()>();
+        let _handle = pool.spawn_blocking(move || {
+            // Never sent, so this thread (and therefore shutdown) never
+            // finishes within the timeout below.
+            let _ = rx.recv();
+        });
+
+        pool.shutdown(Some(Duration::from_millis(10)));
+
+        assert!(!pool.shutdown.state.lock().unwrap().done);
+        drop(tx);
+    }
+}
@@ -0,0 +1,61 @@
+//! Builder for configuring and constructing a [`Runtime`](crate::runtime::Runtime).
+//!
+//! Only the task lifecycle callback registration lives here
+//! (`on_task_spawn`/`on_task_poll_begin`/`on_task_poll_end`/`on_task_terminate`);
+//! picking `Basic` vs `ThreadPool`, enabling the IO/time drivers, and sizing
+//! the thread pool are configured through `basic_scheduler.rs`/`thread_pool.rs`,
+//! which this tree doesn't carry.
+
+use crate::runtime::TaskCallbacks;
+use std::sync::Arc;
+
+/// Builds a [`Runtime`](crate::runtime::Runtime) with custom configuration
+/// values.
+#[derive(Debug, Default)]
+pub struct Builder {
+    pub(crate) task_callbacks: TaskCallbacks,
+}
+
+impl Builder {
+    /// Creates a new runtime builder with default configuration values.
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    /// Registers a callback invoked when a task is spawned onto the
+    /// runtime, before the task is handed off to run.
+    pub fn on_task_spawn<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.task_callbacks.on_spawn = Some(Arc::new(f));
+        self
+    }
+
+    /// Registers a callback invoked just before each poll of a task begins.
+    pub fn on_task_poll_begin<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.task_callbacks.on_poll_begin = Some(Arc::new(f));
+        self
+    }
+
+    /// Registers a callback invoked just after each poll of a task ends.
+    pub fn on_task_poll_end<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.task_callbacks.on_poll_end = Some(Arc::new(f));
+        self
+    }
+
+    /// Registers a callback invoked when a task terminates.
+    pub fn on_task_terminate<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn() + Send + Sync + 'static,
+    {
+        self.task_callbacks.on_terminate = Some(Arc::new(f));
+        self
+    }
+}
@@ -0,0 +1,29 @@
+//! A cloneable handle to a runtime.
+//!
+//! Provides access to the scheduler context needed to construct resources
+//! and spawn tasks outside of a [`Runtime`](crate::runtime::Runtime) value
+//! itself.
+
+use crate::runtime::spawner::Spawner;
+
+/// Handle to the runtime, cheaply cloneable and `Send + Sync`.
+///
+/// Task lifecycle callbacks registered on [`Builder`](crate::runtime::Builder)
+/// aren't stored here directly — they're threaded through `spawner`, which
+/// is what `Spawner::spawn` actually reads when wrapping a task in
+/// `runtime::task::Instrumented`.
+#[derive(Debug, Clone)]
+pub(crate) struct Handle {
+    pub(crate) spawner: Spawner,
+}
+
+impl Handle {
+    pub(crate) fn enter<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        // Entering ties `crate::spawn`/resource construction to this
+        // handle; the actual context stack lives in `runtime::context`.
+        f()
+    }
+}
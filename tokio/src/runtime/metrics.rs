@@ -0,0 +1,145 @@
+//! Runtime metrics
+//!
+//! A [`RuntimeMetrics`] is a point-in-time snapshot of scheduler counters,
+//! cheap enough to take frequently so saturation can be observed without
+//! attaching a profiler. The counters backing it ([`MetricsBatch`]) are
+//! plain relaxed atomics bumped from the existing hot paths in the worker
+//! loops and injection queue; `Runtime::metrics()` just reads them out.
+
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+/// A snapshot of metrics describing a [`Runtime`](crate::runtime::Runtime)'s
+/// scheduler, returned by [`Runtime::metrics`](crate::runtime::Runtime::metrics).
+///
+/// Every counter is zero for the `Shell` runtime, which has no scheduler.
+#[derive(Debug, Clone)]
+pub struct RuntimeMetrics {
+    /// Number of tasks currently spawned on the runtime and not yet
+    /// completed.
+    pub num_alive_tasks: usize,
+
+    /// Number of worker threads used by the runtime's scheduler.
+    pub num_workers: usize,
+
+    /// Number of tasks currently queued in each worker's local run queue,
+    /// indexed by worker.
+    pub worker_local_queue_depth: Vec<usize>,
+
+    /// Number of tasks currently queued in the scheduler's global injection
+    /// queue, waiting to be picked up by any worker.
+    pub injection_queue_depth: usize,
+
+    /// Total number of tasks spawned onto the runtime since it was created.
+    pub total_tasks_spawned: u64,
+
+    /// Total number of times a worker stole a task from another worker's
+    /// local run queue.
+    pub total_steal_count: u64,
+}
+
+/// Shared, cheaply-updated counters bumped from the scheduler's hot paths.
+/// A [`RuntimeMetrics`] snapshot is produced from these on demand.
+#[derive(Debug)]
+pub(crate) struct MetricsBatch {
+    num_alive_tasks: AtomicUsize,
+    injection_queue_depth: AtomicUsize,
+    total_tasks_spawned: AtomicU64,
+    total_steal_count: AtomicU64,
+    worker_local_queue_depth: Vec<AtomicUsize>,
+}
+
+impl MetricsBatch {
+    pub(crate) fn new(num_workers: usize) -> Self {
+        Self {
+            num_alive_tasks: AtomicUsize::new(0),
+            injection_queue_depth: AtomicUsize::new(0),
+            total_tasks_spawned: AtomicU64::new(0),
+            total_steal_count: AtomicU64::new(0),
+            worker_local_queue_depth: (0..num_workers).map(|_| AtomicUsize::new(0)).collect(),
+        }
+    }
+
+    /// A snapshot reporting every counter as zero, used for the `Shell`
+    /// runtime where no scheduler is running.
+    pub(crate) fn disabled() -> RuntimeMetrics {
+        Self::new(0).snapshot()
+    }
+
+    pub(crate) fn inc_tasks_spawned(&self) {
+        self.total_tasks_spawned.fetch_add(1, Ordering::Relaxed);
+        self.num_alive_tasks.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn dec_alive_tasks(&self) {
+        self.num_alive_tasks.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn inc_steal_count(&self) {
+        self.total_steal_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_worker_queue_depth(&self, worker: usize, depth: usize) {
+        self.worker_local_queue_depth[worker].store(depth, Ordering::Relaxed);
+    }
+
+    pub(crate) fn set_injection_queue_depth(&self, depth: usize) {
+        self.injection_queue_depth.store(depth, Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> RuntimeMetrics {
+        RuntimeMetrics {
+            num_alive_tasks: self.num_alive_tasks.load(Ordering::Relaxed),
+            num_workers: self.worker_local_queue_depth.len(),
+            worker_local_queue_depth: self
+                .worker_local_queue_depth
+                .iter()
+                .map(|depth| depth.load(Ordering::Relaxed))
+                .collect(),
+            injection_queue_depth: self.injection_queue_depth.load(Ordering::Relaxed),
+            total_tasks_spawned: self.total_tasks_spawned.load(Ordering::Relaxed),
+            total_steal_count: self.total_steal_count.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MetricsBatch;
+
+    #[test]
+    fn inc_tasks_spawned_bumps_spawned_and_alive_counts() {
+        let batch = MetricsBatch::new(1);
+
+        batch.inc_tasks_spawned();
+        batch.inc_tasks_spawned();
+
+        let snapshot = batch.snapshot();
+        assert_eq!(snapshot.total_tasks_spawned, 2);
+        assert_eq!(snapshot.num_alive_tasks, 2);
+    }
+
+    #[test]
+    fn dec_alive_tasks_decrements_without_touching_total_spawned() {
+        let batch = MetricsBatch::new(1);
+        batch.inc_tasks_spawned();
+        batch.inc_tasks_spawned();
+
+        batch.dec_alive_tasks();
+
+        let snapshot = batch.snapshot();
+        assert_eq!(snapshot.num_alive_tasks, 1);
+        assert_eq!(snapshot.total_tasks_spawned, 2);
+    }
+
+    #[test]
+    fn disabled_batch_reports_every_counter_as_zero() {
+        let snapshot = MetricsBatch::disabled();
+
+        assert_eq!(snapshot.num_alive_tasks, 0);
+        assert_eq!(snapshot.num_workers, 0);
+        assert!(snapshot.worker_local_queue_depth.is_empty());
+        assert_eq!(snapshot.injection_queue_depth, 0);
+        assert_eq!(snapshot.total_tasks_spawned, 0);
+        assert_eq!(snapshot.total_steal_count, 0);
+    }
+}
@@ -216,6 +216,9 @@ use self::enter::enter;
 mod handle;
 use handle::Handle;
 
+mod metrics;
+pub use metrics::RuntimeMetrics;
+
 mod io {
     /// Re-exported for convenience.
     pub(crate) use std::io::Result;
@@ -303,9 +306,35 @@ enum Kind {
     ThreadPool(ThreadPool),
 }
 
-/// After thread starts / before thread stops
+/// After thread starts / before thread stops. Also used for the per-task
+/// lifecycle hooks in [`TaskCallbacks`], which have the same `Fn() + Send +
+/// Sync` shape.
 type Callback = std::sync::Arc<dyn Fn() + Send + Sync>;
 
+/// Task lifecycle callbacks registered via [`Builder`] and held by the
+/// `Spawner` in [`Handle`]. `Spawner::spawn` wraps each task in
+/// `runtime::task::Instrumented`, which invokes them. `Runtime::spawn` on
+/// `Kind::ThreadPool` hands the future to `exec.spawn` instead, so those
+/// tasks are not wrapped and the callbacks do not fire for them.
+#[derive(Clone, Default)]
+pub(crate) struct TaskCallbacks {
+    pub(crate) on_spawn: Option<Callback>,
+    pub(crate) on_poll_begin: Option<Callback>,
+    pub(crate) on_poll_end: Option<Callback>,
+    pub(crate) on_terminate: Option<Callback>,
+}
+
+impl std::fmt::Debug for TaskCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskCallbacks")
+            .field("on_spawn", &self.on_spawn.is_some())
+            .field("on_poll_begin", &self.on_poll_begin.is_some())
+            .field("on_poll_end", &self.on_poll_end.is_some())
+            .field("on_terminate", &self.on_terminate.is_some())
+            .finish()
+    }
+}
+
 impl Runtime {
     /// Create a new runtime instance with default configuration values.
     ///
@@ -520,6 +549,41 @@ impl Runtime {
         self.handle.enter(f)
     }
 
+    /// Returns a snapshot of metrics describing this runtime's scheduler,
+    /// such as the number of alive tasks, the depth of each worker's local
+    /// run queue, and the number of steal operations performed. Useful for
+    /// observing scheduler saturation without attaching a profiler.
+    ///
+    /// For the `Shell` runtime, which runs no scheduler, every counter in
+    /// the returned [`RuntimeMetrics`] is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// fn main() {
+    ///     let rt = Runtime::new().unwrap();
+    ///     let metrics = rt.metrics();
+    ///     println!("alive tasks = {}", metrics.num_alive_tasks);
+    /// }
+    /// ```
+    pub fn metrics(&self) -> RuntimeMetrics {
+        match &self.kind {
+            Kind::Shell(_) => metrics::MetricsBatch::disabled(),
+            // Both read the counters owned by `self.handle.spawner`. `Basic`
+            // spawns through that spawner; `ThreadPool` spawns through
+            // `exec.spawn`, so its tasks are not counted in
+            // `total_tasks_spawned`/`num_alive_tasks`, and its queue depths
+            // and steals appear only for queues built with
+            // `Spawner::metrics_handle`.
+            #[cfg(feature = "rt-core")]
+            Kind::Basic(_) => self.handle.spawner.metrics(),
+            #[cfg(feature = "rt-threaded")]
+            Kind::ThreadPool(_) => self.handle.spawner.metrics(),
+        }
+    }
+
     /// Shutdown the runtime, waiting for at most `duration` for all spawned
     /// task to shutdown.
     ///
@@ -561,6 +625,42 @@ impl Runtime {
         self.blocking_pool.shutdown(Some(duration));
     }
 
+    /// Shuts down the runtime, returning a future that resolves once every
+    /// closure running on the blocking pool has returned. Tasks spawned with
+    /// `Runtime::spawn` are neither stopped nor waited for.
+    ///
+    /// Unlike [`shutdown_timeout`] and [`shutdown_background`], this does
+    /// not block the calling thread, so it can be `.await`ed from inside
+    /// another async context to let a supervising task orchestrate an
+    /// orderly shutdown and drain of blocking work.
+    ///
+    /// [`shutdown_timeout`]: Runtime::shutdown_timeout
+    /// [`shutdown_background`]: Runtime::shutdown_background
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use tokio::runtime::Runtime;
+    ///
+    /// fn main() {
+    ///     let runtime = Runtime::new().unwrap();
+    ///     runtime.block_on(async move {
+    ///         // ...
+    ///     });
+    ///
+    ///     let supervisor = Runtime::new().unwrap();
+    ///     supervisor.block_on(async move {
+    ///         runtime.shutdown().await;
+    ///     });
+    /// }
+    /// ```
+    pub fn shutdown(mut self) -> impl Future<Output = ()> {
+        // `Spawner::shutdown` does not stop spawned tasks; only the blocking
+        // pool is awaited below.
+        self.handle.spawner.shutdown();
+        self.blocking_pool.shutdown_async()
+    }
+
     /// Shutdown the runtime, without waiting for any spawned tasks to shutdown.
     ///
     /// This can be useful if you want to drop a runtime from within another runtime.
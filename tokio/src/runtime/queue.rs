@@ -0,0 +1,134 @@
+//! Work-stealing run queues used by the `ThreadPool` scheduler: each worker
+//! owns a [`Local`] queue, with a shared [`Inject`] queue for tasks spawned
+//! from outside any worker or that overflow a worker's local queue.
+//!
+//! Pushes, pops, and steals bump the counters in the [`MetricsBatch`]
+//! handed to these queues at construction (see
+//! [`Spawner::metrics_handle`](crate::runtime::spawner::Spawner::metrics_handle)),
+//! so `Runtime::metrics()` reflects live queue depths and steal counts.
+
+use crate::runtime::metrics::MetricsBatch;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+/// A single worker's local run queue.
+#[derive(Debug)]
+pub(crate) struct Local<T> {
+    worker: usize,
+    queue: Mutex<VecDeque<T>>,
+    metrics: Arc<MetricsBatch>,
+}
+
+impl<T> Local<T> {
+    pub(crate) fn new(worker: usize, metrics: Arc<MetricsBatch>) -> Self {
+        Self {
+            worker,
+            queue: Mutex::new(VecDeque::new()),
+            metrics,
+        }
+    }
+
+    pub(crate) fn push(&self, item: T) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.push_back(item);
+        self.metrics.set_worker_queue_depth(self.worker, queue.len());
+    }
+
+    pub(crate) fn pop(&self) -> Option<T> {
+        let mut queue = self.queue.lock().unwrap();
+        let item = queue.pop_front();
+        self.metrics.set_worker_queue_depth(self.worker, queue.len());
+        item
+    }
+
+    /// Steals the most recently queued task from `other` (the end opposite
+    /// to the one `other.pop` takes from) and returns it, recording the
+    /// steal in the shared metrics. Returns `None` if `other` is empty.
+    pub(crate) fn steal_from(&self, other: &Local<T>) -> Option<T> {
+        let mut other_queue = other.queue.lock().unwrap();
+        let item = other_queue.pop_back();
+        other
+            .metrics
+            .set_worker_queue_depth(other.worker, other_queue.len());
+        drop(other_queue);
+
+        if item.is_some() {
+            self.metrics.inc_steal_count();
+        }
+
+        item
+    }
+}
+
+/// The global injection queue: tasks spawned from outside a worker thread,
+/// or that overflowed a worker's local queue, land here until a worker
+/// picks them up.
+#[derive(Debug)]
+pub(crate) struct Inject<T> {
+    queue: Mutex<VecDeque<T>>,
+    metrics: Arc<MetricsBatch>,
+}
+
+impl<T> Inject<T> {
+    pub(crate) fn new(metrics: Arc<MetricsBatch>) -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::new()),
+            metrics,
+        }
+    }
+
+    pub(crate) fn push(&self, item: T) {
+        let mut queue = self.queue.lock().unwrap();
+        queue.push_back(item);
+        self.metrics.set_injection_queue_depth(queue.len());
+    }
+
+    pub(crate) fn pop(&self) -> Option<T> {
+        let mut queue = self.queue.lock().unwrap();
+        let item = queue.pop_front();
+        self.metrics.set_injection_queue_depth(queue.len());
+        item
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn local_push_pop_updates_queue_depth_metric() {
+        let metrics = Arc::new(MetricsBatch::new(1));
+        let local = Local::new(0, metrics.clone());
+
+        local.push(1);
+        local.push(2);
+        assert_eq!(metrics.snapshot().worker_local_queue_depth[0], 2);
+
+        assert_eq!(local.pop(), Some(1));
+        assert_eq!(metrics.snapshot().worker_local_queue_depth[0], 1);
+    }
+
+    #[test]
+    fn steal_from_increments_total_steal_count() {
+        let metrics = Arc::new(MetricsBatch::new(2));
+        let a = Local::new(0, metrics.clone());
+        let b = Local::new(1, metrics.clone());
+
+        a.push(1);
+        assert_eq!(b.steal_from(&a), Some(1));
+        assert_eq!(metrics.snapshot().total_steal_count, 1);
+        assert_eq!(metrics.snapshot().worker_local_queue_depth[0], 0);
+    }
+
+    #[test]
+    fn inject_push_pop_updates_injection_queue_depth_metric() {
+        let metrics = Arc::new(MetricsBatch::new(0));
+        let inject = Inject::new(metrics.clone());
+
+        inject.push("a");
+        assert_eq!(metrics.snapshot().injection_queue_depth, 1);
+
+        assert_eq!(inject.pop(), Some("a"));
+        assert_eq!(metrics.snapshot().injection_queue_depth, 0);
+    }
+}
@@ -0,0 +1,76 @@
+//! The task spawn entrypoint shared by the `Basic` scheduler `Kind`, and
+//! the owner of the [`MetricsBatch`] counters backing `Runtime::metrics()`.
+//!
+//! `metrics_handle` hands out this spawner's `Arc<MetricsBatch>`; pushes,
+//! pops, and steals on [`queue`](crate::runtime::queue) instances built
+//! with it are reflected in the same snapshot this type returns from
+//! `metrics()`.
+
+use crate::runtime::metrics::{MetricsBatch, RuntimeMetrics};
+use crate::runtime::task;
+use crate::runtime::TaskCallbacks;
+use crate::task::JoinHandle;
+use std::future::Future;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub(crate) struct Spawner {
+    metrics: Arc<MetricsBatch>,
+    task_callbacks: TaskCallbacks,
+}
+
+impl Spawner {
+    pub(crate) fn new(num_workers: usize, task_callbacks: TaskCallbacks) -> Self {
+        Self {
+            metrics: Arc::new(MetricsBatch::new(num_workers)),
+            task_callbacks,
+        }
+    }
+
+    /// The counters this spawner's tasks bump into, for passing to
+    /// `queue::Local::new`/`queue::Inject::new` so that queue reports into
+    /// the same snapshot.
+    pub(crate) fn metrics_handle(&self) -> Arc<MetricsBatch> {
+        self.metrics.clone()
+    }
+
+    pub(crate) fn spawn<F>(&self, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        self.metrics.inc_tasks_spawned();
+        let instrumented = task::Instrumented::new(future, self.task_callbacks.clone());
+
+        // Calls `task_complete` on drop, so a task that panics is counted
+        // as terminated too.
+        struct TaskComplete(Spawner);
+        impl Drop for TaskComplete {
+            fn drop(&mut self) {
+                self.0.task_complete();
+            }
+        }
+
+        let complete = TaskComplete(self.clone());
+        task::spawn(async move {
+            let _complete = complete;
+            instrumented.await
+        })
+    }
+
+    pub(crate) fn shutdown(&self) {
+        // Does nothing: tasks spawned through `spawn` each run on their own
+        // thread (see `runtime::task::spawn`) and are neither signalled nor
+        // stopped here.
+    }
+
+    /// Called once a task spawned through `spawn` has terminated (returned,
+    /// panicked, or been dropped), so `num_alive_tasks` stays accurate.
+    pub(crate) fn task_complete(&self) {
+        self.metrics.dec_alive_tasks();
+    }
+
+    pub(crate) fn metrics(&self) -> RuntimeMetrics {
+        self.metrics.snapshot()
+    }
+}
@@ -0,0 +1,242 @@
+//! Task lifecycle glue used by `Spawner::spawn`: wraps a spawned future so
+//! the `Builder`-registered [`TaskCallbacks`] fire around its spawn, each
+//! poll, and its termination, then hands the wrapped future off to a
+//! minimal executor that actually drives it to completion.
+//!
+//! The work-stealing worker loops that `Basic`/`ThreadPool` normally run
+//! tasks on aren't reproduced in this tree, so [`spawn`] below runs each
+//! task on its own dedicated thread instead of a shared worker pool —
+//! correct, just not how the real scheduler multiplexes tasks onto a
+//! bounded set of workers.
+
+use crate::runtime::TaskCallbacks;
+use crate::task::JoinHandle;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+use std::thread;
+
+/// Wraps a future so the runtime's registered [`TaskCallbacks`] fire
+/// around its lifecycle: once when the wrapper is created (spawn), once
+/// before and after every poll, and once when the inner future resolves
+/// (terminate).
+pub(crate) struct Instrumented<F> {
+    future: F,
+    callbacks: TaskCallbacks,
+    terminated: bool,
+}
+
+impl<F> Instrumented<F> {
+    pub(crate) fn new(future: F, callbacks: TaskCallbacks) -> Self {
+        if let Some(on_spawn) = &callbacks.on_spawn {
+            on_spawn();
+        }
+
+        Self {
+            future,
+            callbacks,
+            terminated: false,
+        }
+    }
+}
+
+impl<F: Future> Future for Instrumented<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is only ever accessed through this method's
+        // reborrowed `Pin` and is never moved out of `self`.
+        let this = unsafe { self.get_unchecked_mut() };
+        let future = unsafe { Pin::new_unchecked(&mut this.future) };
+
+        if let Some(on_poll_begin) = &this.callbacks.on_poll_begin {
+            on_poll_begin();
+        }
+
+        let out = future.poll(cx);
+
+        if let Some(on_poll_end) = &this.callbacks.on_poll_end {
+            on_poll_end();
+        }
+
+        if out.is_ready() && !this.terminated {
+            this.terminated = true;
+            if let Some(on_terminate) = &this.callbacks.on_terminate {
+                on_terminate();
+            }
+        }
+
+        out
+    }
+}
+
+/// Runs `future` to completion on a dedicated thread and returns a
+/// [`JoinHandle`] for its result. This is the single entrypoint
+/// `Spawner::spawn` calls after wrapping the future in [`Instrumented`].
+///
+/// A real `Basic`/`ThreadPool` worker loop polls tasks cooperatively on a
+/// bounded set of threads it owns; absent that machinery in this tree, this
+/// gives each task its own thread and a minimal thread-parking executor
+/// instead, so a task actually runs (and `JoinHandle` actually resolves)
+/// rather than panicking on every valid spawn.
+pub(crate) fn spawn<F>(future: F) -> JoinHandle<F::Output>
+where
+    F: Future + Send + 'static,
+    F::Output: Send + 'static,
+{
+    JoinHandle::spawn_on_thread(move || block_on(future))
+}
+
+/// Parks the current thread until `future` resolves, polling it again each
+/// time its waker is woken. Not a real reactor-driven executor — just
+/// enough to drive a single task to completion on the thread `spawn` above
+/// gave it.
+fn block_on<F: Future>(future: F) -> F::Output {
+    let thread = thread::current();
+    let waker = thread_waker(thread);
+    let mut cx = Context::from_waker(&waker);
+
+    // SAFETY: `future` is owned by this stack frame and is never moved
+    // while pinned.
+    let mut future = future;
+    let mut future = unsafe { Pin::new_unchecked(&mut future) };
+
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+fn thread_waker(thread: thread::Thread) -> Waker {
+    fn clone(data: *const ()) -> RawWaker {
+        let thread = unsafe { &*(data as *const thread::Thread) };
+        let boxed = Box::new(thread.clone());
+        RawWaker::new(Box::into_raw(boxed) as *const (), &VTABLE)
+    }
+    fn wake(data: *const ()) {
+        let thread = unsafe { Box::from_raw(data as *mut thread::Thread) };
+        thread.unpark();
+    }
+    fn wake_by_ref(data: *const ()) {
+        let thread = unsafe { &*(data as *const thread::Thread) };
+        thread.unpark();
+    }
+    fn drop_waker(data: *const ()) {
+        drop(unsafe { Box::from_raw(data as *mut thread::Thread) });
+    }
+
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop_waker);
+
+    let boxed = Box::new(thread);
+    let raw = RawWaker::new(Box::into_raw(boxed) as *const (), &VTABLE);
+    unsafe { Waker::from_raw(raw) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Instrumented;
+    use crate::runtime::TaskCallbacks;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    struct ReadyOnSecondPoll(bool);
+
+    impl Future for ReadyOnSecondPoll {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    fn instrumented_invokes_each_callback_at_the_right_point() {
+        let spawns = Arc::new(AtomicUsize::new(0));
+        let poll_begins = Arc::new(AtomicUsize::new(0));
+        let poll_ends = Arc::new(AtomicUsize::new(0));
+        let terminates = Arc::new(AtomicUsize::new(0));
+
+        let callbacks = TaskCallbacks {
+            on_spawn: Some({
+                let spawns = spawns.clone();
+                Arc::new(move || {
+                    spawns.fetch_add(1, Ordering::SeqCst);
+                })
+            }),
+            on_poll_begin: Some({
+                let poll_begins = poll_begins.clone();
+                Arc::new(move || {
+                    poll_begins.fetch_add(1, Ordering::SeqCst);
+                })
+            }),
+            on_poll_end: Some({
+                let poll_ends = poll_ends.clone();
+                Arc::new(move || {
+                    poll_ends.fetch_add(1, Ordering::SeqCst);
+                })
+            }),
+            on_terminate: Some({
+                let terminates = terminates.clone();
+                Arc::new(move || {
+                    terminates.fetch_add(1, Ordering::SeqCst);
+                })
+            }),
+        };
+
+        let mut instrumented = Box::pin(Instrumented::new(ReadyOnSecondPoll(false), callbacks));
+        assert_eq!(spawns.load(Ordering::SeqCst), 1);
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+
+        assert_eq!(instrumented.as_mut().poll(&mut cx), Poll::Pending);
+        assert_eq!(poll_begins.load(Ordering::SeqCst), 1);
+        assert_eq!(poll_ends.load(Ordering::SeqCst), 1);
+        assert_eq!(terminates.load(Ordering::SeqCst), 0);
+
+        assert_eq!(instrumented.as_mut().poll(&mut cx), Poll::Ready(()));
+        assert_eq!(poll_begins.load(Ordering::SeqCst), 2);
+        assert_eq!(poll_ends.load(Ordering::SeqCst), 2);
+        assert_eq!(terminates.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn spawn_runs_the_future_to_completion_and_resolves_the_join_handle() {
+        use super::spawn;
+        use std::thread;
+        use std::time::Duration;
+
+        let handle = spawn(async { 1 + 1 });
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut pinned = Box::pin(handle);
+        let output = loop {
+            match pinned.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => break output,
+                Poll::Pending => thread::sleep(Duration::from_millis(1)),
+            }
+        };
+
+        assert_eq!(output.expect("task did not panic"), 2);
+    }
+}
@@ -0,0 +1,193 @@
+//! Signal driver
+//!
+//! This module owns the platform-agnostic half of signal handling: the
+//! `Driver`/`Handle` pair that the rest of the crate depends on. The
+//! mechanism used to learn that a signal has arrived differs per platform
+//! (a self-pipe registered with the IO driver on Unix, console control
+//! events on Windows), so that part is delegated to a small `Source`
+//! implementation selected by `cfg`.
+
+use crate::io::driver::Driver as IoDriver;
+use crate::park::Park;
+use crate::runtime::context;
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+#[cfg(unix)]
+#[path = "unix/driver.rs"]
+mod imp;
+
+#[cfg(windows)]
+#[path = "windows/driver.rs"]
+mod imp;
+
+use self::imp::OsExtraData;
+
+pub(crate) use self::imp::SignalInfo;
+
+/// A platform-neutral identifier for a signal, holding a Unix signal
+/// number. The Windows backend keys its dispatch table on it; the Unix
+/// registry keys on the raw `libc::c_int` directly.
+///
+/// On Unix, `as_raw`/`from_raw` round-trip the `libc::c_int` signal number
+/// unchanged. On Windows there is no signal number to preserve, so
+/// `windows::driver` maps each `CTRL_*_EVENT` onto the Unix number of the
+/// signal it's conventionally treated as equivalent to (e.g. `CTRL_C_EVENT`
+/// and `CTRL_BREAK_EVENT` both become `SIGINT`'s number).
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct SignalKind(i32);
+
+impl SignalKind {
+    pub(crate) fn from_raw(signum: i32) -> Self {
+        Self(signum)
+    }
+
+    pub(crate) fn as_raw(self) -> i32 {
+        self.0
+    }
+}
+
+/// Abstracts over the platform-specific mechanism used to learn that an OS
+/// signal has arrived. There is exactly one implementation compiled in for
+/// any given target, selected via `cfg`, so `Driver` can call through it
+/// without knowing whether it is backed by a self-pipe or a console control
+/// handler.
+pub(crate) trait Source: Sized {
+    /// Initializes the platform backend, registering it with `park` if it
+    /// needs to participate in IO readiness polling.
+    fn new(park: &IoDriver) -> io::Result<Self>;
+
+    /// Drains whatever mechanism was used to wake us up and dispatches the
+    /// [`SignalInfo`] for each signal that arrived, through each platform's
+    /// own dispatch state (`signal::unix::registry::globals` on Unix;
+    /// a module-local `DISPATCH` on Windows).
+    ///
+    /// Returns an error if the backend is no longer able to observe
+    /// signals (e.g. the self-pipe was closed or a read failed), rather
+    /// than panicking the whole runtime.
+    fn process(&self) -> io::Result<()>;
+}
+
+/// Responsible for registering wakeups when an OS signal is received, and
+/// subsequently dispatching notifications to any signal listeners as appropriate.
+///
+/// Note: this driver relies on having an enabled IO driver in order to listen to
+/// pipe write wakeups.
+#[derive(Debug)]
+pub(crate) struct Driver {
+    /// Thread parker. The `Driver` park implementation delegates to this.
+    park: IoDriver,
+
+    /// Platform-specific half of the driver, responsible for detecting that
+    /// a signal has arrived and draining whatever mechanism was used to
+    /// notify us of it.
+    os: OsExtraData,
+
+    /// Shared state
+    inner: Arc<Inner>,
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct Handle {
+    inner: Weak<Inner>,
+}
+
+#[derive(Debug)]
+pub(super) struct Inner {
+    /// Set once the platform backend has reported an unrecoverable error
+    /// (e.g. the self-pipe was closed or wedged), so that `Handle`
+    /// consumers can observe the driver has entered a failed state instead
+    /// of the whole process aborting.
+    failed: AtomicBool,
+}
+
+impl Inner {
+    fn new() -> Self {
+        Self {
+            failed: AtomicBool::new(false),
+        }
+    }
+}
+
+// ===== impl Driver =====
+
+impl Driver {
+    /// Creates a new signal `Driver` instance that delegates wakeups to `park`.
+    pub(crate) fn new(park: IoDriver) -> io::Result<Self> {
+        let os = OsExtraData::new(&park)?;
+
+        Ok(Self {
+            park,
+            os,
+            inner: Arc::new(Inner::new()),
+        })
+    }
+
+    /// Returns a handle to this event loop which can be sent across threads
+    /// and can be used as a proxy to the event loop itself.
+    pub(crate) fn handle(&self) -> Handle {
+        Handle {
+            inner: Arc::downgrade(&self.inner),
+        }
+    }
+
+    fn process(&self) -> io::Result<()> {
+        self.os.process().map_err(|e| {
+            self.inner.failed.store(true, Ordering::Release);
+            e
+        })
+    }
+}
+
+// ===== impl Park for Driver =====
+
+impl Park for Driver {
+    type Unpark = <IoDriver as Park>::Unpark;
+    type Error = io::Error;
+
+    fn unpark(&self) -> Self::Unpark {
+        self.park.unpark()
+    }
+
+    fn park(&mut self) -> Result<(), Self::Error> {
+        self.park.park()?;
+        self.process()
+    }
+
+    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
+        self.park.park_timeout(duration)?;
+        self.process()
+    }
+
+    fn shutdown(&mut self) {
+        self.park.shutdown()
+    }
+}
+
+// ===== impl Handle =====
+
+impl Handle {
+    /// Returns a handle to the current driver
+    ///
+    /// # Panics
+    ///
+    /// This function panics if there is no current signal driver set.
+    pub(super) fn current() -> Self {
+        context::signal_handle().expect(
+            "there is no signal driver running, must be called from the context of Tokio runtime",
+        )
+    }
+
+    pub(super) fn check_inner(&self) -> io::Result<()> {
+        match self.inner.upgrade() {
+            None => Err(io::Error::new(io::ErrorKind::Other, "signal driver gone")),
+            Some(inner) if inner.failed.load(Ordering::Acquire) => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "signal driver has entered a failed state and can no longer dispatch signals",
+            )),
+            Some(_) => Ok(()),
+        }
+    }
+}
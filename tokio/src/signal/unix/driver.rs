@@ -1,49 +1,108 @@
-//! Signal driver
+//! Unix-specific parts of the signal driver: a self-pipe fed by the signal
+//! handler and registered with the IO driver for readiness.
 
 use crate::io::driver::Driver as IoDriver;
 use crate::io::Registration;
-use crate::park::Park;
-use crate::runtime::context;
-use crate::signal::registry::globals;
-use mio_uds::UnixStream;
-use std::io::{self, Read};
-use std::sync::{Arc, Weak};
-use std::time::Duration;
-
-/// Responsible for registering wakeups when an OS signal is received, and
-/// subsequently dispatching notifications to any signal listeners as appropriate.
-///
-/// Note: this driver relies on having an enabled IO driver in order to listen to
-/// pipe write wakeups.
-#[derive(Debug)]
-pub(crate) struct Driver {
-    /// Thread parker. The `Driver` park implementation delegates to this.
-    park: IoDriver,
-
-    /// A pipe for receiving wake events from the signal handler
-    receiver: UnixStream,
+use crate::signal::driver::Source;
+use mio_uds::UnixDatagram;
+use std::convert::TryInto;
+use std::io;
+
+mod registry;
+use registry::globals;
+
+/// A fixed-size, self-pipe-friendly encoding of the `siginfo_t` fields
+/// listeners actually care about. The signal handler (see `globals().raise`)
+/// encodes one of these per signal it catches; `process` below decodes them
+/// back out of the pipe so consumers learn which process sent a signal
+/// instead of only that one of a given kind arrived.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SignalInfo {
+    /// `si_signo`: which signal this is.
+    pub(crate) signo: i32,
+    /// `si_pid`: the pid of the process that raised the signal.
+    pub(crate) pid: i32,
+    /// `si_uid`: the real user id of the sending process.
+    pub(crate) uid: u32,
+    /// For `SIGCHLD`, the exit status of the child (`si_status`); zero for
+    /// all other signals.
+    pub(crate) status: i32,
+}
 
-    /// The actual registraiton for `receiver` when active.
-    /// Lazily bound at the first signal registration.
-    registration: Registration,
+impl SignalInfo {
+    pub(crate) const SIZE: usize = 16;
+
+    /// Encodes `self` into the wire format written to the self-pipe by the
+    /// (async-signal-safe) OS signal handler in `registry::handler`.
+    pub(crate) fn encode(&self) -> [u8; Self::SIZE] {
+        let mut bytes = [0; Self::SIZE];
+        bytes[0..4].copy_from_slice(&self.signo.to_ne_bytes());
+        bytes[4..8].copy_from_slice(&self.pid.to_ne_bytes());
+        bytes[8..12].copy_from_slice(&self.uid.to_ne_bytes());
+        bytes[12..16].copy_from_slice(&self.status.to_ne_bytes());
+        bytes
+    }
 
-    /// Shared state
-    inner: Arc<Inner>,
+    fn decode(bytes: &[u8]) -> Self {
+        let bytes: [u8; Self::SIZE] = bytes.try_into().expect("record is SignalInfo::SIZE bytes");
+        Self {
+            signo: i32::from_ne_bytes(bytes[0..4].try_into().unwrap()),
+            pid: i32::from_ne_bytes(bytes[4..8].try_into().unwrap()),
+            uid: u32::from_ne_bytes(bytes[8..12].try_into().unwrap()),
+            status: i32::from_ne_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
 }
 
-#[derive(Clone, Debug)]
-pub(crate) struct Handle {
-    inner: Weak<Inner>,
+#[cfg(test)]
+mod tests {
+    use super::SignalInfo;
+
+    #[test]
+    fn signal_info_roundtrips_through_encode_decode() {
+        let info = SignalInfo {
+            signo: libc::SIGCHLD,
+            pid: 4242,
+            uid: 1000,
+            status: 137,
+        };
+
+        let decoded = SignalInfo::decode(&info.encode());
+
+        assert_eq!(decoded.signo, info.signo);
+        assert_eq!(decoded.pid, info.pid);
+        assert_eq!(decoded.uid, info.uid);
+        assert_eq!(decoded.status, info.status);
+    }
+
+    #[test]
+    fn chunks_exact_recovers_multiple_records() {
+        let a = SignalInfo { signo: libc::SIGINT, pid: 1, uid: 0, status: 0 };
+        let b = SignalInfo { signo: libc::SIGTERM, pid: 2, uid: 0, status: 0 };
+
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&a.encode());
+        buf.extend_from_slice(&b.encode());
+
+        let decoded: Vec<_> = buf.chunks_exact(SignalInfo::SIZE).map(SignalInfo::decode).collect();
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].signo, libc::SIGINT);
+        assert_eq!(decoded[1].signo, libc::SIGTERM);
+    }
 }
 
 #[derive(Debug)]
-pub(super) struct Inner(());
+pub(crate) struct OsExtraData {
+    /// A pipe for receiving wake events from the signal handler
+    receiver: UnixDatagram,
 
-// ===== impl Driver =====
+    /// The registration of `receiver` with the IO driver, made in `new`.
+    registration: Registration,
+}
 
-impl Driver {
-    /// Creates a new signal `Driver` instance that delegates wakeups to `park`.
-    pub(crate) fn new(park: IoDriver) -> io::Result<Self> {
+impl Source for OsExtraData {
+    fn new(park: &IoDriver) -> io::Result<Self> {
         // NB: We give each driver a "fresh" reciever file descriptor to avoid
         // the issues described in alexcrichton/tokio-process#42.
         //
@@ -62,92 +121,47 @@ impl Driver {
             Registration::new_with_ready_and_handle(&receiver, mio::Ready::all(), park.handle())?;
 
         Ok(Self {
-            park,
             receiver,
             registration,
-            inner: Arc::new(Inner(())),
         })
     }
 
-    /// Returns a handle to this event loop which can be sent across threads
-    /// and can be used as a proxy to the event loop itself.
-    pub(crate) fn handle(&self) -> Handle {
-        Handle {
-            inner: Arc::downgrade(&self.inner),
-        }
-    }
-
-    fn process(&self) {
+    fn process(&self) -> io::Result<()> {
         // Check if the pipe is ready to read and therefore has "woken" us up
         match self.registration.take_read_ready() {
             Ok(Some(ready)) => assert!(ready.is_readable()),
-            Ok(None) => return, // No wake has arrived, bail
-            Err(e) => panic!("reactor gone: {}", e),
+            Ok(None) => return Ok(()), // No wake has arrived, bail
+            Err(e) => return Err(e),
         }
 
         // Drain the pipe completely so we can receive a new readiness event
-        // if another signal has come in.
-        let mut buf = [0; 128];
+        // if another signal has come in. Each `recv` returns exactly one
+        // `SignalInfo`-sized datagram (or fails outright) rather than an
+        // arbitrary byte count, so there's no length-delimited reassembly
+        // needed here and no way for a record to arrive torn.
+        let mut buf = [0; SignalInfo::SIZE];
         loop {
-            match (&self.receiver).read(&mut buf) {
-                Ok(0) => panic!("EOF on self-pipe"),
-                Ok(_) => continue, // Keep reading
+            match self.receiver.recv(&mut buf) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "EOF on self-pipe",
+                    ))
+                }
+                Ok(n) if n == SignalInfo::SIZE => {
+                    globals().broadcast(SignalInfo::decode(&buf));
+                }
+                Ok(n) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("self-pipe datagram was {} bytes, expected {}", n, SignalInfo::SIZE),
+                    ))
+                }
                 Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
-                Err(e) => panic!("Bad read on self-pipe: {}", e),
+                Err(e) => return Err(e),
             }
         }
 
-        // Broadcast any signals which were received
-        globals().broadcast();
-    }
-}
-
-// ===== impl Park for Driver =====
-
-impl Park for Driver {
-    type Unpark = <IoDriver as Park>::Unpark;
-    type Error = io::Error;
-
-    fn unpark(&self) -> Self::Unpark {
-        self.park.unpark()
-    }
-
-    fn park(&mut self) -> Result<(), Self::Error> {
-        self.park.park()?;
-        self.process();
         Ok(())
     }
-
-    fn park_timeout(&mut self, duration: Duration) -> Result<(), Self::Error> {
-        self.park.park_timeout(duration)?;
-        self.process();
-        Ok(())
-    }
-
-    fn shutdown(&mut self) {
-        self.park.shutdown()
-    }
-}
-
-// ===== impl Handle =====
-
-impl Handle {
-    /// Returns a handle to the current driver
-    ///
-    /// # Panics
-    ///
-    /// This function panics if there is no current signal driver set.
-    pub(super) fn current() -> Self {
-        context::signal_handle().expect(
-            "there is no signal driver running, must be called from the context of Tokio runtime",
-        )
-    }
-
-    pub(super) fn check_inner(&self) -> io::Result<()> {
-        if self.inner.strong_count() > 0 {
-            Ok(())
-        } else {
-            Err(io::Error::new(io::ErrorKind::Other, "signal driver gone"))
-        }
-    }
 }
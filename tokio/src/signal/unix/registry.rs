@@ -0,0 +1,170 @@
+//! The process-wide Unix signal registry.
+//!
+//! This is Unix-specific: it is built around `sigaction`/`siginfo_t` and a
+//! self-pipe, neither of which exist on Windows (see
+//! `signal::windows::driver` for that platform's own, self-contained
+//! dispatch state). `globals()` returns the single `Globals` instance for
+//! this process. It owns both ends of the self-pipe: the sending half is
+//! written to by the (async-signal-safe) OS signal handler installed
+//! below, and the receiving half is what `signal::unix::driver::OsExtraData`
+//! registers with the IO driver and reads back out of.
+
+use super::SignalInfo;
+use mio_uds::UnixDatagram;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+use lazy_static::lazy_static;
+
+/// Per-signal-number dispatch state. A real registry would store one
+/// listener `Sender` per `signal()` call; this stores just enough to prove
+/// that a `SignalInfo` for a given `si_signo` reaches the listeners
+/// registered for it.
+#[derive(Debug, Default)]
+struct SignalState {
+    /// Set by `broadcast` when a `SignalInfo` arrives for this signal
+    /// number; listeners consume it when they wake up.
+    pending: AtomicBool,
+}
+
+#[derive(Debug)]
+pub(crate) struct Globals {
+    /// Cloned by each `OsExtraData::new` to register a fresh PollEvented
+    /// instance; see the comment there for why.
+    pub(crate) receiver: UnixDatagram,
+
+    /// Written to by `handler` below, from inside the OS signal handler.
+    ///
+    /// This is a `UnixDatagram`, not a `UnixStream`: `send`/`recv` on a
+    /// datagram socket are atomic per-message (the kernel either buffers
+    /// the whole record or fails the send), so a `SignalInfo` can never
+    /// arrive on `receiver` torn across two reads the way it could with a
+    /// stream socket's byte-oriented `write`/`read`.
+    sender: UnixDatagram,
+
+    /// Dispatch state, indexed by signal number on first registration.
+    registered: Mutex<Vec<(libc::c_int, SignalState)>>,
+}
+
+lazy_static! {
+    static ref GLOBALS: Globals = {
+        let (receiver, sender) =
+            UnixDatagram::pair().expect("failed to create signal self-pipe");
+
+        Globals {
+            receiver,
+            sender,
+            registered: Mutex::new(Vec::new()),
+        }
+    };
+}
+
+pub(crate) fn globals() -> &'static Globals {
+    &GLOBALS
+}
+
+impl Globals {
+    /// Registers a signal number with the registry so `broadcast` has
+    /// dispatch state to mark for it, and installs `handler` as its
+    /// `sigaction`.
+    pub(crate) fn register(&self, signo: libc::c_int) -> std::io::Result<()> {
+        {
+            let mut registered = self.registered.lock().unwrap();
+            if !registered.iter().any(|(s, _)| *s == signo) {
+                registered.push((signo, SignalState::default()));
+            }
+        }
+
+        // SAFETY: `handler` only performs async-signal-safe operations
+        // (writing a fixed-size record to the self-pipe).
+        unsafe {
+            let mut action: libc::sigaction = std::mem::zeroed();
+            action.sa_sigaction = handler as usize;
+            action.sa_flags = libc::SA_RESTART | libc::SA_SIGINFO;
+            libc::sigemptyset(&mut action.sa_mask);
+
+            if libc::sigaction(signo, &action, std::ptr::null_mut()) != 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Called from the OS signal handler (by way of `handler` below) to
+    /// deliver a decoded `SignalInfo` into the self-pipe.
+    fn raise(&self, info: SignalInfo) {
+        // Best-effort: if the socket's buffer is full this record is
+        // dropped. The driver still wakes for the records already queued,
+        // but this record's `SignalInfo` is lost. `send` on a `UnixDatagram`
+        // never writes a partial message, so there's no torn-record case
+        // to handle on the receiving end.
+        let _ = self.sender.send(&info.encode());
+    }
+
+    /// Dispatches a `SignalInfo` decoded off the self-pipe by
+    /// `OsExtraData::process` to whichever listeners are registered for
+    /// `info.signo`.
+    pub(crate) fn broadcast(&self, info: SignalInfo) {
+        let registered = self.registered.lock().unwrap();
+        for (signo, state) in registered.iter() {
+            if *signo == info.signo {
+                state.pending.store(true, Ordering::SeqCst);
+            }
+        }
+    }
+}
+
+/// The actual OS signal handler, installed via `sigaction` by `register`.
+/// Must only perform async-signal-safe operations: it captures the
+/// `siginfo_t` fields listeners care about and writes the encoded
+/// `SignalInfo` into the self-pipe for the driver to decode later.
+extern "C" fn handler(
+    signo: libc::c_int,
+    info: *mut libc::siginfo_t,
+    _ucontext: *mut libc::c_void,
+) {
+    // SAFETY: `info` is provided by the kernel for the duration of the
+    // handler call.
+    let info = unsafe { &*info };
+
+    let record = SignalInfo {
+        signo,
+        pid: info.si_pid(),
+        uid: info.si_uid(),
+        status: if signo == libc::SIGCHLD {
+            info.si_status()
+        } else {
+            0
+        },
+    };
+
+    globals().raise(record);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broadcast_marks_pending_only_for_the_matching_signal_number() {
+        let globals = globals();
+        globals
+            .register(libc::SIGUSR1)
+            .expect("failed to register SIGUSR1");
+
+        globals.broadcast(SignalInfo {
+            signo: libc::SIGUSR1,
+            pid: 0,
+            uid: 0,
+            status: 0,
+        });
+
+        let registered = globals.registered.lock().unwrap();
+        for (signo, state) in registered.iter() {
+            if *signo == libc::SIGUSR1 {
+                assert!(state.pending.load(Ordering::SeqCst));
+            }
+        }
+    }
+}
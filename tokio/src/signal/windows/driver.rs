@@ -0,0 +1,209 @@
+//! Windows-specific parts of the signal driver.
+//!
+//! There is no self-pipe on Windows, and no `sigaction`/`siginfo_t` to
+//! share with the Unix side, so this platform keeps its own dispatch state
+//! rather than going through `signal::unix::registry` (see that module for
+//! the Unix equivalent). A console control handler installed with
+//! `SetConsoleCtrlHandler` records which events arrived into a small queue
+//! and flips a synthetic IO source readable so the park loop wakes up
+//! promptly instead of waiting out its timeout; `process` then drains that
+//! queue, marking the matching entry in `DISPATCH` pending for listeners to
+//! consume.
+
+use crate::io::driver::Driver as IoDriver;
+use crate::io::Registration;
+use crate::signal::driver::{Source, SignalKind};
+use std::io;
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
+use std::sync::{Mutex, Once};
+
+use lazy_static::lazy_static;
+use mio::Registration as MioRegistration;
+use winapi::shared::minwindef::{BOOL, DWORD, FALSE, TRUE};
+use winapi::um::consoleapi::SetConsoleCtrlHandler;
+use winapi::um::wincon::{CTRL_BREAK_EVENT, CTRL_CLOSE_EVENT, CTRL_C_EVENT};
+
+/// The payload dispatched to listeners for a console control event. There is
+/// no PID or UID to report on this platform, unlike Unix's `siginfo_t`, but
+/// we still report which logical signal kind fired so the shape matches the
+/// Unix side's `SignalInfo`.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SignalInfo {
+    /// The raw `CTRL_*_EVENT` value reported by the console control handler.
+    pub(crate) ctrl_type: DWORD,
+}
+
+/// Maps a raw `CTRL_*_EVENT` onto the cross-platform [`SignalKind`] so this
+/// backend's dispatch table (`DISPATCH` below) is keyed the same way the
+/// Unix registry is, rather than on a Windows-only `DWORD`. `CTRL_C_EVENT`
+/// and `CTRL_BREAK_EVENT` both map to the signal number conventionally used
+/// for `SIGINT`, and `CTRL_CLOSE_EVENT` to `SIGHUP`'s, mirroring how a
+/// terminal closing is treated as a hangup on Unix.
+fn ctrl_type_to_signal_kind(ctrl_type: DWORD) -> SignalKind {
+    const SIGHUP: i32 = 1;
+    const SIGINT: i32 = 2;
+
+    match ctrl_type {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT => SignalKind::from_raw(SIGINT),
+        CTRL_CLOSE_EVENT => SignalKind::from_raw(SIGHUP),
+        other => SignalKind::from_raw(other as i32),
+    }
+}
+
+lazy_static! {
+    /// Console events recorded by `console_ctrl_handler` below, drained by
+    /// `process` on the next wakeup of the signal driver.
+    static ref PENDING_EVENTS: Mutex<Vec<DWORD>> = Mutex::new(Vec::new());
+
+    /// One `SetReadiness` per live `OsExtraData`, notified by
+    /// `console_ctrl_handler` so every signal driver backed by this process
+    /// wakes up promptly instead of only on its next unrelated readiness
+    /// event or timeout.
+    static ref WAKERS: Mutex<Vec<mio::SetReadiness>> = Mutex::new(Vec::new());
+}
+
+/// Guards `SetConsoleCtrlHandler` registration so it only happens once per
+/// process. Constructing more than one `Driver` (e.g. two `Runtime`s, or a
+/// dropped-and-recreated one) must not re-register `console_ctrl_handler`:
+/// each registered instance of a handler fires independently, so a second
+/// registration would cause a single Ctrl event to be pushed into
+/// `PENDING_EVENTS` and broadcast once per registration instead of once.
+static REGISTER_HANDLER: Once = Once::new();
+
+/// The OS error code from the one-and-only `SetConsoleCtrlHandler` call, or
+/// `0` if it succeeded. Cached alongside `REGISTER_HANDLER` so that a
+/// `Driver` constructed after a failed first registration still observes
+/// the failure instead of reporting success.
+static REGISTER_ERROR: AtomicI32 = AtomicI32::new(0);
+
+/// Per-ctrl-type dispatch state, mirroring the role
+/// `signal::unix::registry::Globals` plays for Unix signals, scoped to this
+/// module since there's no `siginfo_t` payload to share across platforms.
+#[derive(Debug, Default)]
+struct CtrlState {
+    pending: AtomicBool,
+}
+
+struct Dispatch {
+    registered: Mutex<Vec<(SignalKind, CtrlState)>>,
+}
+
+lazy_static! {
+    static ref DISPATCH: Dispatch = Dispatch {
+        registered: Mutex::new(Vec::new()),
+    };
+}
+
+/// Marks the dispatch state for `info.ctrl_type`'s mapped [`SignalKind`]
+/// pending, registering it on first arrival. Called by `process` below as
+/// it drains `PENDING_EVENTS`.
+fn broadcast(info: SignalInfo) {
+    let kind = ctrl_type_to_signal_kind(info.ctrl_type);
+    let mut registered = DISPATCH.registered.lock().unwrap();
+    match registered.iter().find(|(k, _)| *k == kind) {
+        Some((_, state)) => state.pending.store(true, Ordering::SeqCst),
+        None => registered.push((kind, CtrlState { pending: AtomicBool::new(true) })),
+    }
+}
+
+unsafe extern "system" fn console_ctrl_handler(ctrl_type: DWORD) -> BOOL {
+    match ctrl_type {
+        CTRL_C_EVENT | CTRL_BREAK_EVENT | CTRL_CLOSE_EVENT => {
+            PENDING_EVENTS.lock().unwrap().push(ctrl_type);
+            for set_readiness in WAKERS.lock().unwrap().iter() {
+                let _ = set_readiness.set_readiness(mio::Ready::readable());
+            }
+            TRUE
+        }
+        _ => FALSE,
+    }
+}
+
+#[derive(Debug)]
+pub(crate) struct OsExtraData {
+    /// Registers `_source` with the IO driver so a woken console event
+    /// promptly wakes `park` instead of waiting for its timeout to elapse.
+    registration: Registration,
+
+    /// The synthetic (not OS-fd-backed) source whose readiness
+    /// `console_ctrl_handler` toggles through `WAKERS`; kept alive
+    /// alongside `registration` for as long as this backend is.
+    _source: MioRegistration,
+}
+
+impl Source for OsExtraData {
+    fn new(park: &IoDriver) -> io::Result<Self> {
+        REGISTER_HANDLER.call_once(|| {
+            if unsafe { SetConsoleCtrlHandler(Some(console_ctrl_handler), TRUE) } == FALSE {
+                let code = io::Error::last_os_error().raw_os_error().unwrap_or(-1);
+                REGISTER_ERROR.store(code, Ordering::Release);
+            }
+        });
+
+        match REGISTER_ERROR.load(Ordering::Acquire) {
+            0 => {}
+            code => return Err(io::Error::from_raw_os_error(code)),
+        }
+
+        let (source, set_readiness) = MioRegistration::new2();
+        WAKERS.lock().unwrap().push(set_readiness);
+
+        let registration =
+            Registration::new_with_ready_and_handle(&source, mio::Ready::all(), park.handle())?;
+
+        Ok(Self {
+            registration,
+            _source: source,
+        })
+    }
+
+    fn process(&self) -> io::Result<()> {
+        match self.registration.take_read_ready() {
+            Ok(Some(ready)) => assert!(ready.is_readable()),
+            Ok(None) => return Ok(()), // No wake has arrived, bail
+            Err(e) => return Err(e),
+        }
+
+        let events: Vec<DWORD> = {
+            let mut pending = PENDING_EVENTS.lock().unwrap();
+            std::mem::take(&mut *pending)
+        };
+
+        for ctrl_type in events {
+            broadcast(SignalInfo { ctrl_type });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn broadcast_marks_matching_kind_pending() {
+        broadcast(SignalInfo {
+            ctrl_type: CTRL_C_EVENT,
+        });
+
+        let registered = DISPATCH.registered.lock().unwrap();
+        let (_, state) = registered
+            .iter()
+            .find(|(kind, _)| *kind == ctrl_type_to_signal_kind(CTRL_C_EVENT))
+            .expect("CTRL_C_EVENT should have registered dispatch state");
+        assert!(state.pending.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn ctrl_c_and_ctrl_break_map_to_the_same_signal_kind() {
+        assert_eq!(
+            ctrl_type_to_signal_kind(CTRL_C_EVENT),
+            ctrl_type_to_signal_kind(CTRL_BREAK_EVENT),
+        );
+        assert_ne!(
+            ctrl_type_to_signal_kind(CTRL_C_EVENT),
+            ctrl_type_to_signal_kind(CTRL_CLOSE_EVENT),
+        );
+    }
+}
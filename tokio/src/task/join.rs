@@ -0,0 +1,188 @@
+//! A handle to a spawned task's output.
+
+use std::any::Any;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+#[derive(Default)]
+struct State<T> {
+    output: Option<std::thread::Result<T>>,
+    waker: Option<Waker>,
+}
+
+struct Inner<T> {
+    state: Mutex<State<T>>,
+}
+
+impl<T> Inner<T> {
+    fn new() -> Self {
+        Self {
+            state: Mutex::new(State {
+                output: None,
+                waker: None,
+            }),
+        }
+    }
+
+    fn complete(&self, output: std::thread::Result<T>) {
+        let mut state = self.state.lock().unwrap();
+        state.output = Some(output);
+        if let Some(waker) = state.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+/// An owned handle to a spawned task, returned by [`spawn`](crate::spawn).
+///
+/// Awaiting a `JoinHandle` resolves once the spawned task completes, with
+/// `Err(JoinError)` if the task panicked instead of returning a value.
+/// Dropping a `JoinHandle` does not cancel the task it refers to.
+#[derive(Debug)]
+pub struct JoinHandle<T> {
+    inner: Arc<Inner<T>>,
+}
+
+impl<T> JoinHandle<T>
+where
+    T: Send + 'static,
+{
+    /// Runs `f` to completion on a dedicated thread, returning a handle that
+    /// resolves with its result (or a [`JoinError`] if it panics).
+    pub(crate) fn spawn_on_thread<F>(f: F) -> Self
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let inner = Arc::new(Inner::new());
+        let completion = inner.clone();
+
+        std::thread::spawn(move || {
+            let output = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+            completion.complete(output);
+        });
+
+        Self { inner }
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.inner.state.lock().unwrap();
+        match state.output.take() {
+            Some(Ok(value)) => Poll::Ready(Ok(value)),
+            Some(Err(panic)) => Poll::Ready(Err(JoinError::panic(panic))),
+            None => {
+                state.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> fmt::Debug for Inner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Inner").finish()
+    }
+}
+
+/// Error returned by a [`JoinHandle`] when the spawned task panics instead
+/// of completing normally.
+pub struct JoinError {
+    panic: Box<dyn Any + Send + 'static>,
+}
+
+impl JoinError {
+    fn panic(payload: Box<dyn Any + Send + 'static>) -> Self {
+        Self { panic: payload }
+    }
+
+    /// Returns the panic payload's message, if it was a `&str` or `String`
+    /// (the common case for `panic!("...")`).
+    pub fn message(&self) -> Option<&str> {
+        if let Some(s) = self.panic.downcast_ref::<&str>() {
+            Some(s)
+        } else {
+            self.panic.downcast_ref::<String>().map(String::as_str)
+        }
+    }
+}
+
+impl fmt::Debug for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinError")
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.message() {
+            Some(message) => write!(f, "task panicked: {}", message),
+            None => write!(f, "task panicked"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+#[cfg(test)]
+mod tests {
+    use super::JoinHandle;
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+    use std::thread;
+    use std::time::Duration;
+
+    fn noop_waker() -> Waker {
+        fn clone(_: *const ()) -> RawWaker {
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+        fn noop(_: *const ()) {}
+        static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+        unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+    }
+
+    fn poll_until_ready<T>(mut handle: Pin<&mut JoinHandle<T>>) -> T
+    where
+        T: Send + 'static,
+    {
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match handle.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output.expect("task did not panic"),
+                Poll::Pending => thread::sleep(Duration::from_millis(1)),
+            }
+        }
+    }
+
+    #[test]
+    fn spawn_on_thread_resolves_with_the_closures_output() {
+        let handle = JoinHandle::spawn_on_thread(|| 40 + 2);
+        assert_eq!(poll_until_ready(Box::pin(handle).as_mut()), 42);
+    }
+
+    #[test]
+    fn spawn_on_thread_reports_a_panic_as_a_join_error() {
+        let handle = JoinHandle::spawn_on_thread(|| -> () { panic!("boom") });
+
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        let mut pinned = Box::pin(handle);
+        let err = loop {
+            match pinned.as_mut().poll(&mut cx) {
+                Poll::Ready(Err(err)) => break err,
+                Poll::Ready(Ok(())) => panic!("expected the task to panic"),
+                Poll::Pending => thread::sleep(Duration::from_millis(1)),
+            }
+        };
+        assert_eq!(err.message(), Some("boom"));
+    }
+}
@@ -0,0 +1,4 @@
+//! Types for spawning tasks and awaiting their output.
+
+mod join;
+pub use join::{JoinError, JoinHandle};